@@ -14,6 +14,8 @@
 
 use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::num::IntErrorKind;
 use std::num::ParseIntError;
@@ -41,6 +43,10 @@ pub struct Error<'a> {
     pub contexts: Vec<(Range, &'static str)>,
     /// The extra backtrace of error in optional branches.
     pub backtrace: &'a Backtrace,
+    /// Whether this error is unrecoverable, i.e. it was raised past a commit
+    /// point (see [`cut`]) and must not be discarded or merged by `or`/`alt` in
+    /// favor of an unrelated, farther-reaching alternative.
+    pub cut: bool,
 }
 
 /// ErrorKind is the error type returned from parser.
@@ -89,6 +95,7 @@ impl<'a> nom::error::ParseError<Input<'a>> for Error<'a> {
             errors: vec![],
             contexts: vec![],
             backtrace: i.1,
+            cut: false,
         }
     }
 
@@ -101,6 +108,16 @@ impl<'a> nom::error::ParseError<Input<'a>> for Error<'a> {
     }
 
     fn or(mut self, mut other: Self) -> Self {
+        // Once an error has been cut (raised past a commit point), it must win
+        // over any uncut alternative regardless of how far that alternative got,
+        // otherwise a later, more specific failure is masked by an unrelated
+        // branch that merely consumed more input.
+        match (self.cut, other.cut) {
+            (true, false) => return self,
+            (false, true) => return other,
+            _ => {}
+        }
+
         match self.span.start.cmp(&other.span.start) {
             Ordering::Equal => {
                 self.errors.append(&mut other.errors);
@@ -150,8 +167,32 @@ impl<'a> Error<'a> {
             errors: vec![kind],
             contexts: vec![],
             backtrace: input.1,
+            cut: false,
         }
     }
+
+    /// Marks this error as unrecoverable, so that `or`/`alt` will never discard or
+    /// merge it in favor of an unrelated alternative (see the `or` impl above).
+    pub fn cut(mut self) -> Self {
+        self.cut = true;
+        self
+    }
+}
+
+/// Commits to the current alternative once an unambiguous prefix has already been
+/// consumed (e.g. the parser is past `$[` and is now inside a bracket selector), so
+/// a later failure can't be overridden by an unrelated, farther-reaching branch.
+/// Mirrors winnow's `ErrMode::Cut`; wrap the bracket-selector, filter, and
+/// function-call parsers with this once their prefix is unambiguous.
+pub fn cut<'a, O>(
+    mut parser: impl FnMut(Input<'a>) -> nom::IResult<Input<'a>, O, Error<'a>>,
+) -> impl FnMut(Input<'a>) -> nom::IResult<Input<'a>, O, Error<'a>> {
+    move |input| {
+        parser(input).map_err(|err| match err {
+            nom::Err::Error(err) => nom::Err::Error(err.cut()),
+            err => err,
+        })
+    }
 }
 
 impl From<fast_float::Error> for ErrorKind {
@@ -174,29 +215,576 @@ impl From<ParseIntError> for ErrorKind {
     }
 }
 
-pub fn display_parser_error(error: Error, source: &str) -> String {
-    let inner = &*error.backtrace.inner.borrow();
-    let inner = match inner {
-        Some(inner) => inner,
-        None => return String::new(),
-    };
+/// Buffers diagnostics produced while parsing in error-recovery mode, where the
+/// parser keeps going past a syntax error (skipping ahead to the next `.`, `[`, or
+/// `,` boundary token) instead of aborting on the first one.
+///
+/// Errors are keyed by their starting offset in an ordered map, following the same
+/// de-duplication strategy rustc's borrow checker uses for buffered diagnostics:
+/// if a new error's span starts at the same place as one already buffered, only
+/// the more specific of the two (the narrower span) is kept, rather than
+/// appending both. The buffer is flushed in source order once parsing finishes.
+#[derive(Debug, Default)]
+pub struct ErrorAccumulator<'a> {
+    pending: BTreeMap<usize, Error<'a>>,
+}
+
+impl<'a> ErrorAccumulator<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffers `error`, replacing any previously buffered error that starts at the
+    /// same position if `error` is the more specific (narrower) of the two.
+    pub fn push(&mut self, error: Error<'a>) {
+        match self.pending.entry(error.span.start) {
+            Entry::Vacant(entry) => {
+                entry.insert(error);
+            }
+            Entry::Occupied(mut entry) => {
+                if error.span.end <= entry.get().span.end {
+                    entry.insert(error);
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Flushes the buffered errors in source order.
+    pub fn finish(self) -> Vec<Error<'a>> {
+        self.pending.into_values().collect()
+    }
+}
+
+/// Renders each of `errors` the same way [`display_parser_error`] would, in the
+/// source order they were collected in (see [`ErrorAccumulator`]).
+pub fn display_parser_errors(errors: Vec<Error>, source: &str) -> Vec<String> {
+    errors
+        .into_iter()
+        .map(|error| display_parser_error(error, source))
+        .filter(|msg| !msg.is_empty())
+        .collect()
+}
+
+/// Boundary token kinds a recovery-mode parse resyncs to after a syntax error,
+/// rather than giving up on the rest of the expression.
+const RECOVERY_BOUNDARIES: &[TokenKind] = &[Dot, LBracket, Comma];
+
+/// Runs `parse` against `input` in opt-in error-recovery mode: this is the
+/// top-level entry point a caller switches to when it wants every mistake in a
+/// JSONPath expression reported at once, instead of bailing out on the first
+/// one. On failure, the error is buffered in an [`ErrorAccumulator`] and parsing
+/// resumes at the next `.`, `[`, or `,` boundary token *at or after where the
+/// error was actually reported*, so fixing one error doesn't just uncover
+/// another error at the very front of the expression.
+///
+/// Note: this driver is not itself a commit point for [`cut`] — `cut` is meant
+/// to be applied inside the bracket-selector, filter, and function-call
+/// parsers so that `alt`'s internal branch resolution (`or`) isn't fooled by
+/// an unrelated, farther-reaching alternative. Those grammar parsers aren't
+/// part of this tree, so no such call site exists yet; wrapping the *outcome*
+/// of an already-resolved `parse` call here would be too late to affect which
+/// branch won and was deliberately left out.
+///
+/// Returns the output of the last successful attempt (if parsing ever
+/// succeeded) alongside every error collected along the way, in source order.
+pub fn parse_with_recovery<'a, O>(
+    mut input: Input<'a>,
+    mut parse: impl FnMut(Input<'a>) -> nom::IResult<Input<'a>, O, Error<'a>>,
+) -> (Option<O>, Vec<Error<'a>>) {
+    let mut errors = ErrorAccumulator::new();
+    let mut output = None;
+
+    loop {
+        // Reset the farthest-position backtrace shared through `input.1` so a
+        // stale `BacktraceInner` left by an earlier, abandoned attempt can't
+        // leak into this attempt's diagnostic.
+        input.1.clear();
+
+        match parse(input) {
+            Ok((_, parsed)) => {
+                output = Some(parsed);
+                break;
+            }
+            Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => {
+                let resync_from = err.span.start;
+                errors.push(err);
+                match skip_to_boundary(input, resync_from) {
+                    Some(next) => input = next,
+                    None => break,
+                }
+            }
+            Err(nom::Err::Incomplete(_)) => break,
+        }
+    }
+
+    (output, errors.finish())
+}
+
+/// Advances to the first `.`, `[`, `,`, or end-of-input boundary token whose
+/// span starts at or after `resync_from` (the position the failed parse
+/// actually reported its error at, not just wherever `input` happened to
+/// start), so a recovery-mode retry resumes past the mistake instead of
+/// re-scanning from the front of the current slice. Returns `None` once
+/// there's nothing left to skip to.
+fn skip_to_boundary(input: Input<'_>, resync_from: usize) -> Option<Input<'_>> {
+    let tokens = input.0;
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let offset = tokens.iter().position(|token| {
+        token.span.start >= resync_from
+            && (RECOVERY_BOUNDARIES.contains(&token.kind) || token.kind == Eoi)
+    })?;
+    // The boundary found is the very token the error was reported at: step past
+    // it so every retry makes forward progress instead of looping forever.
+    let offset = if offset == 0 { 1 } else { offset };
+
+    if offset >= tokens.len() {
+        None
+    } else {
+        Some(Input(&tokens[offset..], input.1))
+    }
+}
+
+#[cfg(test)]
+mod recovery_tests {
+    use super::*;
+
+    fn range(start: usize, end: usize) -> Range {
+        Range { start, end }
+    }
+
+    fn error(backtrace: &Backtrace, start: usize, end: usize, msg: &'static str) -> Error<'_> {
+        Error {
+            span: range(start, end),
+            errors: vec![ErrorKind::Other(msg)],
+            contexts: vec![],
+            backtrace,
+            cut: false,
+        }
+    }
+
+    #[test]
+    fn keeps_narrower_span_at_the_same_start() {
+        let backtrace = Backtrace::new();
+        let mut acc = ErrorAccumulator::new();
+
+        acc.push(error(&backtrace, 4, 10, "wide"));
+        acc.push(error(&backtrace, 4, 6, "narrow"));
+        // A wider span pushed after a narrower one at the same start must not
+        // replace the narrower, more specific one.
+        acc.push(error(&backtrace, 4, 20, "widest"));
+
+        let finished = acc.finish();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].span, range(4, 6));
+    }
+
+    #[test]
+    fn flushes_in_source_order_regardless_of_push_order() {
+        let backtrace = Backtrace::new();
+        let mut acc = ErrorAccumulator::new();
+
+        acc.push(error(&backtrace, 10, 12, "second"));
+        acc.push(error(&backtrace, 0, 2, "first"));
+
+        let finished = acc.finish();
+        assert_eq!(
+            finished.iter().map(|e| e.span.start).collect::<Vec<_>>(),
+            vec![0, 10]
+        );
+    }
+}
+
+#[cfg(test)]
+mod skip_to_boundary_tests {
+    use super::*;
+
+    fn token(kind: TokenKind, start: usize, end: usize) -> Token<'static> {
+        Token {
+            kind,
+            span: range(start, end),
+            text: "",
+        }
+    }
+
+    fn range(start: usize, end: usize) -> Range {
+        Range { start, end }
+    }
+
+    #[test]
+    fn resyncs_to_the_first_boundary_at_or_after_the_error_span() {
+        let backtrace = Backtrace::new();
+        // An ident and a `.`, then a bogus ident and a `[`, with the reported
+        // error starting where the bogus ident does (offset 6): the `.` just
+        // before it is *before* the error and must be skipped over, not
+        // resynced to.
+        let tokens = [
+            token(Ident, 0, 5),
+            token(Dot, 5, 6),
+            token(Ident, 6, 10),
+            token(LBracket, 10, 11),
+            token(Eoi, 11, 11),
+        ];
+        let input = Input(&tokens, &backtrace);
+
+        let next = skip_to_boundary(input, 6).expect("a later boundary exists");
+        assert_eq!(next.0[0].kind, LBracket);
+        assert_eq!(next.0[0].span, range(10, 11));
+    }
+
+    #[test]
+    fn steps_past_a_boundary_token_that_is_itself_the_error() {
+        let backtrace = Backtrace::new();
+        // The error is reported right at the `.`, so resyncing to that same
+        // token would make no forward progress; it must step past it instead.
+        let tokens = [
+            token(Dot, 0, 1),
+            token(Ident, 1, 5),
+            token(Comma, 5, 6),
+            token(Eoi, 6, 6),
+        ];
+        let input = Input(&tokens, &backtrace);
+
+        let next = skip_to_boundary(input, 0).expect("tokens remain after the dot");
+        assert_eq!(next.0[0].kind, Ident);
+        assert_eq!(next.0[0].span, range(1, 5));
+    }
+
+    #[test]
+    fn returns_none_once_stepping_past_the_only_boundary_exhausts_the_input() {
+        let backtrace = Backtrace::new();
+        let tokens = [token(Dot, 0, 1)];
+        let input = Input(&tokens, &backtrace);
+
+        assert!(skip_to_boundary(input, 0).is_none());
+    }
+
+    #[test]
+    fn returns_none_when_no_boundary_exists_at_or_after_the_resync_point() {
+        let backtrace = Backtrace::new();
+        let tokens = [token(Ident, 0, 3)];
+        let input = Input(&tokens, &backtrace);
+
+        assert!(skip_to_boundary(input, 0).is_none());
+    }
+}
+
+/// A structured parse diagnostic, as an alternative to the pre-rendered string
+/// returned by [`display_parser_error`]. Exposing the raw position, expected-token
+/// list and context labels lets callers that embed the JSONPath parser (language
+/// servers, web playgrounds, SQL engines) consume error information
+/// programmatically instead of scraping the rendered string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    /// The primary span the error is reported at.
+    pub span: Range,
+    /// The expected tokens/texts, already rendered (e.g. `` `]` ``, `<Ident>` ``),
+    /// in the order they were encountered. Empty when `message` is set.
+    pub expected: Vec<String>,
+    /// Secondary labels describing the parse context, innermost first.
+    pub contexts: Vec<(Range, String)>,
+    /// A plain-text message overriding `expected`, set when the error originates
+    /// from [`ErrorKind::Other`].
+    pub message: Option<String>,
+    /// The closest expected keyword/text to the source text at `span`, if any is
+    /// within a small Levenshtein edit distance (see [`did_you_mean`]).
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    /// Renders this diagnostic the same way [`display_parser_error`] does.
+    pub fn render(&self, source: &str) -> String {
+        let mut labels = vec![];
+
+        if let Some(msg) = &self.message {
+            labels.push((self.span, msg.clone()));
+        } else {
+            labels.push((self.span, format_expected_tokens(&self.expected)));
+        }
+        labels.extend(self.suggestion_label());
+        labels.extend(self.contexts.iter().cloned());
+
+        pretty_print_error(source, labels)
+    }
+
+    /// The `help: did you mean ...` label for [`Self::suggestion`], if any.
+    fn suggestion_label(&self) -> Option<(Range, String)> {
+        self.suggestion
+            .as_ref()
+            .map(|suggestion| (self.span, format!("help: did you mean `{}`?", suggestion)))
+    }
+}
+
+/// Converts a [`Diagnostic`] into a `codespan-reporting` diagnostic, so embedders
+/// that already render their own diagnostics through `codespan-reporting` (or
+/// merge several reports together) can fold a JSONPath parse failure in alongside
+/// the rest, with proper severity and labelled spans instead of a pre-rendered
+/// string. Kept behind a feature flag so the core crate stays dependency-light.
+#[cfg(feature = "codespan-reporting")]
+impl From<&Diagnostic> for codespan_reporting::diagnostic::Diagnostic<()> {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        use codespan_reporting::diagnostic::Label;
+
+        let message = diagnostic
+            .message
+            .clone()
+            .unwrap_or_else(|| format_expected_tokens(&diagnostic.expected));
+
+        let mut labels = vec![
+            Label::primary((), diagnostic.span.start..diagnostic.span.end).with_message(message),
+        ];
+        if let Some(suggestion) = &diagnostic.suggestion {
+            labels.push(
+                Label::secondary((), diagnostic.span.start..diagnostic.span.end)
+                    .with_message(format!("did you mean `{}`?", suggestion)),
+            );
+        }
+        labels.extend(diagnostic.contexts.iter().map(|(span, msg)| {
+            Label::secondary((), span.start..span.end).with_message(msg.clone())
+        }));
+
+        codespan_reporting::diagnostic::Diagnostic::error().with_labels(labels)
+    }
+}
 
-    let mut labels = vec![];
+/// Lets a [`Diagnostic`] be reported through `miette`'s rich terminal renderer
+/// alongside an embedder's other diagnostics. Kept behind a feature flag so the
+/// core crate stays dependency-light.
+#[cfg(feature = "miette")]
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(msg) => write!(f, "{msg}"),
+            None => write!(f, "{}", format_expected_tokens(&self.expected)),
+        }
+    }
+}
+
+#[cfg(feature = "miette")]
+impl std::error::Error for Diagnostic {}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Diagnostic {
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let primary_message = self
+            .message
+            .clone()
+            .unwrap_or_else(|| format_expected_tokens(&self.expected));
+        let primary = miette::LabeledSpan::new(
+            Some(primary_message),
+            self.span.start,
+            self.span.end.saturating_sub(self.span.start),
+        );
+
+        let suggestion = self.suggestion.as_ref().map(|suggestion| {
+            miette::LabeledSpan::new(
+                Some(format!("did you mean `{}`?", suggestion)),
+                self.span.start,
+                self.span.end.saturating_sub(self.span.start),
+            )
+        });
 
-    // Plain text error has the highest priority. Only display it if exists.
-    for kind in &inner.errors {
-        if let ErrorKind::Other(msg) = kind {
-            labels = vec![(inner.span, msg.to_string())];
+        let secondary = self.contexts.iter().map(|(span, msg)| {
+            miette::LabeledSpan::new(
+                Some(msg.clone()),
+                span.start,
+                span.end.saturating_sub(span.start),
+            )
+        });
+
+        Some(Box::new(
+            std::iter::once(primary).chain(suggestion).chain(secondary),
+        ))
+    }
+}
+
+/// Extracts the literal keyword/text candidates out of an already-rendered
+/// `expected` list, skipping entries that describe a token *category* (e.g.
+/// `<Ident>`) rather than a literal spelling.
+fn expected_literals(expected: &[String]) -> impl Iterator<Item = &str> {
+    expected
+        .iter()
+        .filter_map(|s| s.strip_prefix('`')?.strip_suffix('`'))
+}
+
+/// Computes the Levenshtein edit distance between two strings.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let prev_up = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j - 1]).min(prev_up)
+            };
+            prev_diag = prev_up;
+        }
+    }
+    row[b.len()]
+}
+
+/// Formats a list of already-rendered expected tokens/texts into a single
+/// `expected X, Y or Z` message, capped at [`MAX_DISPLAY_ERROR_COUNT`] entries.
+fn format_expected_tokens(expected_tokens: &[String]) -> String {
+    let mut msg = String::new();
+    let mut iter = expected_tokens.iter().enumerate().peekable();
+    while let Some((i, token)) = iter.next() {
+        if i == MAX_DISPLAY_ERROR_COUNT {
+            let more = expected_tokens
+                .len()
+                .saturating_sub(MAX_DISPLAY_ERROR_COUNT);
+            write!(msg, ", or {} more ...", more).unwrap();
             break;
+        } else if i == 0 {
+            msg += "expected ";
+        } else if iter.peek().is_none() && i == 1 {
+            msg += " or ";
+        } else if iter.peek().is_none() {
+            msg += ", or ";
+        } else {
+            msg += ", ";
         }
+        msg += token;
     }
+    msg
+}
+
+/// Builds a [`Diagnostic`] from `error`, or `None` if `error` is not cut and its
+/// backtrace has recorded no error (i.e. there is nothing to report). A cut
+/// error always yields a diagnostic. `source` is used only to compute the
+/// `did you mean` suggestion, not to validate `error`'s spans.
+pub fn collect_parser_error(error: &Error, source: &str) -> Option<Diagnostic> {
+    // A cut error has already committed to a single branch, so the expected-token
+    // set accumulated across unrelated alternatives in the backtrace no longer
+    // applies: only the cut error's own errors and context are relevant.
+    if error.cut {
+        return Some(build_diagnostic(
+            error.span,
+            &error.errors,
+            &error.contexts,
+            source,
+        ));
+    }
+
+    let inner = &*error.backtrace.inner.borrow();
+    let inner = inner.as_ref()?;
+
+    let errors = error
+        .errors
+        .iter()
+        .chain(&inner.errors)
+        .copied()
+        .collect::<Vec<_>>();
+    Some(build_diagnostic(inner.span, &errors, &error.contexts, source))
+}
+
+/// If the source text at `span` is a near-miss (small Levenshtein edit distance)
+/// of one of the `expected` keywords/texts, returns the closest match, e.g. for
+/// `lenght()` against an expected `length` this returns `Some("length")`.
+fn did_you_mean(span: Range, expected: &[String], source: &str) -> Option<String> {
+    let actual = source.get(span.start..span.end)?;
+    if actual.is_empty() {
+        return None;
+    }
+
+    expected_literals(expected)
+        .filter(|candidate| !candidate.eq_ignore_ascii_case(actual))
+        .filter_map(|candidate| {
+            let distance = levenshtein_distance(actual, candidate);
+            let threshold = (candidate.chars().count() / 3).max(2);
+            (distance > 0 && distance <= threshold).then_some((candidate, distance))
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate.to_string())
+}
+
+#[cfg(test)]
+mod did_you_mean_tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distance_counts_substitutions_and_length_differences() {
+        assert_eq!(levenshtein_distance("length", "length"), 0);
+        // transposed "th" -> "ht": two substitutions.
+        assert_eq!(levenshtein_distance("lenght", "length"), 2);
+        assert_eq!(levenshtein_distance("len", "length"), 3);
+    }
+
+    #[test]
+    fn did_you_mean_suggests_the_closest_expected_literal_within_threshold() {
+        let source = "$.lenght()";
+        let span = Range { start: 2, end: 8 };
+        let expected = vec!["`length`".to_string(), "<Ident>".to_string()];
+
+        assert_eq!(
+            did_you_mean(span, &expected, source),
+            Some("length".to_string())
+        );
+    }
+
+    #[test]
+    fn did_you_mean_skips_a_case_insensitive_exact_match() {
+        let source = "$.LENGTH()";
+        let span = Range { start: 2, end: 8 };
+        let expected = vec!["`length`".to_string()];
+
+        assert_eq!(did_you_mean(span, &expected, source), None);
+    }
+
+    #[test]
+    fn did_you_mean_rejects_candidates_past_the_edit_distance_threshold() {
+        let source = "$.zzzzzz()";
+        let span = Range { start: 2, end: 8 };
+        let expected = vec!["`length`".to_string()];
+
+        assert_eq!(did_you_mean(span, &expected, source), None);
+    }
+
+    #[test]
+    fn did_you_mean_ignores_token_category_candidates() {
+        let source = "$.lenght()";
+        let span = Range { start: 2, end: 8 };
+        // `<Ident>` describes a token category, not a literal spelling, so it
+        // must never be offered as a suggestion even though nothing else matches.
+        let expected = vec!["<Ident>".to_string()];
+
+        assert_eq!(did_you_mean(span, &expected, source), None);
+    }
+}
+
+/// Builds a [`Diagnostic`] out of a primary span, the errors collected at that
+/// span, and the parsing context labels.
+fn build_diagnostic(
+    span: Range,
+    errors: &[ErrorKind],
+    contexts: &[(Range, &'static str)],
+    source: &str,
+) -> Diagnostic {
+    // Plain text error has the highest priority. Only use it if it exists.
+    let message = errors.iter().find_map(|kind| match kind {
+        ErrorKind::Other(msg) => Some(msg.to_string()),
+        _ => None,
+    });
 
     // List all expected tokens in alternative branches.
-    if labels.is_empty() {
-        let expected_tokens = error
-            .errors
+    let expected = if message.is_some() {
+        vec![]
+    } else {
+        errors
             .iter()
-            .chain(&inner.errors)
             .filter_map(|kind| match kind {
                 ErrorKind::ExpectToken(Eoi) => None,
                 ErrorKind::ExpectToken(token) if token.is_keyword() => {
@@ -207,39 +795,32 @@ pub fn display_parser_error(error: Error, source: &str) -> String {
                 _ => None,
             })
             .unique()
-            .collect::<Vec<_>>();
-
-        let mut msg = String::new();
-        let mut iter = expected_tokens.iter().enumerate().peekable();
-        while let Some((i, error)) = iter.next() {
-            if i == MAX_DISPLAY_ERROR_COUNT {
-                let more = expected_tokens
-                    .len()
-                    .saturating_sub(MAX_DISPLAY_ERROR_COUNT);
-                write!(msg, ", or {} more ...", more).unwrap();
-                break;
-            } else if i == 0 {
-                msg += "expected ";
-            } else if iter.peek().is_none() && i == 1 {
-                msg += " or ";
-            } else if iter.peek().is_none() {
-                msg += ", or ";
-            } else {
-                msg += ", ";
-            }
-            msg += error;
-        }
+            .collect::<Vec<_>>()
+    };
 
-        labels = vec![(inner.span, msg)];
-    }
+    let contexts = contexts
+        .iter()
+        .map(|(span, msg)| (*span, format!("while parsing {}", msg)))
+        .collect();
 
-    // Append contexts as secondary labels.
-    labels.extend(
-        error
-            .contexts
-            .iter()
-            .map(|(span, msg)| (*span, format!("while parsing {}", msg))),
-    );
+    let suggestion = if message.is_none() {
+        did_you_mean(span, &expected, source)
+    } else {
+        None
+    };
 
-    pretty_print_error(source, labels)
+    Diagnostic {
+        span,
+        expected,
+        contexts,
+        message,
+        suggestion,
+    }
+}
+
+pub fn display_parser_error(error: Error, source: &str) -> String {
+    match collect_parser_error(&error, source) {
+        Some(diagnostic) => diagnostic.render(source),
+        None => String::new(),
+    }
 }
\ No newline at end of file